@@ -0,0 +1,206 @@
+//! # Cache
+//!
+//! Offline cache for fetched feeds: stores each source's raw XML keyed by a hash of its
+//! url, together with a fetch timestamp, so articles stay browsable without a network
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::helpers::file as file_helper;
+use crate::helpers::hash as hash_helper;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Default time-to-live, in seconds, before a cached feed is considered stale
+pub const DEFAULT_TTL: i64 = 3600;
+
+/// ### CacheEntry
+///
+/// A single cached feed: the file its raw XML was written to and when it was fetched
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CacheEntry {
+    pub file: PathBuf,
+    pub fetched_at: i64,
+}
+
+/// ### CacheIndex
+///
+/// Maps a source name to its `CacheEntry`; persisted as `index.toml` in the cache dir
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CacheIndex {
+    #[serde(default)]
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl CacheIndex {
+    /// ### load
+    ///
+    /// Load the cache index from `cache_dir`, starting empty if it doesn't exist yet
+    pub fn load(cache_dir: &Path) -> Result<Self, String> {
+        let index_file = index_path(cache_dir);
+        if !index_file.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read_to_string(index_file).map_err(|e| e.to_string())?;
+        toml::from_str(&data).map_err(|e| e.to_string())
+    }
+
+    /// ### save
+    ///
+    /// Persist the cache index to `cache_dir`
+    pub fn save(&self, cache_dir: &Path) -> Result<(), String> {
+        let data = toml::to_string(self).map_err(|e| e.to_string())?;
+        file_helper::write_file(index_path(cache_dir).as_path(), &data).map_err(|e| e.to_string())
+    }
+
+    /// ### is_fresh
+    ///
+    /// Whether `source_name`'s cached entry is younger than `ttl` seconds, relative to `now`
+    pub fn is_fresh(&self, source_name: &str, ttl: i64, now: i64) -> bool {
+        self.entries
+            .get(source_name)
+            .map(|entry| now - entry.fetched_at < ttl)
+            .unwrap_or(false)
+    }
+
+    /// ### has_entry
+    ///
+    /// Whether a (possibly stale) cached copy exists for `source_name`, used to serve
+    /// articles offline when a fresh fetch fails
+    pub fn has_entry(&self, source_name: &str) -> bool {
+        self.entries.contains_key(source_name)
+    }
+
+    /// ### put
+    ///
+    /// Write `xml` to the cache dir for `source_name`/`source_url` and update the index
+    pub fn put(
+        &mut self,
+        cache_dir: &Path,
+        source_name: &str,
+        source_url: &str,
+        xml: &str,
+        now: i64,
+    ) -> Result<(), String> {
+        let mut file = PathBuf::from(cache_dir);
+        file.push(format!("{}.xml", hash_url(source_url)));
+        file_helper::write_file(file.as_path(), xml).map_err(|e| e.to_string())?;
+        self.entries.insert(
+            source_name.to_string(),
+            CacheEntry {
+                file,
+                fetched_at: now,
+            },
+        );
+        self.save(cache_dir)
+    }
+
+    /// ### read
+    ///
+    /// Read the cached raw XML for `source_name`, if any
+    pub fn read(&self, source_name: &str) -> Result<Option<String>, String> {
+        match self.entries.get(source_name) {
+            Some(entry) => std::fs::read_to_string(&entry.file)
+                .map(Some)
+                .map_err(|e| e.to_string()),
+            None => Ok(None),
+        }
+    }
+}
+
+/// ### index_path
+///
+/// Path of the index file within `cache_dir`
+fn index_path(cache_dir: &Path) -> PathBuf {
+    let mut p = PathBuf::from(cache_dir);
+    p.push("index.toml");
+    p
+}
+
+/// ### hash_url
+///
+/// Hash a source url into a filesystem-safe cache key. Uses `hash_helper`'s stable FNV-1a
+/// hash rather than `DefaultHasher`, whose algorithm isn't guaranteed stable across Rust
+/// releases and would invalidate the on-disk cache on every toolchain upgrade
+fn hash_url(url: &str) -> String {
+    hash_helper::fnv1a_hex(url)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_put_and_read_cache_entry() {
+        let cache_dir = std::env::temp_dir();
+        let mut index = CacheIndex::default();
+        assert!(index
+            .put(
+                cache_dir.as_path(),
+                "New York Times",
+                "https://rss.nytimes.com/services/xml/rss/nyt/World.xml",
+                "<rss></rss>",
+                1_000,
+            )
+            .is_ok());
+        assert_eq!(
+            index.read("New York Times").ok().unwrap(),
+            Some("<rss></rss>".to_string())
+        );
+        let entry = index.entries.get("New York Times").unwrap().clone();
+        assert!(std::fs::remove_file(entry.file.as_path()).is_ok());
+        assert!(std::fs::remove_file(index_path(cache_dir.as_path())).is_ok());
+    }
+
+    #[test]
+    fn should_report_freshness_against_ttl() {
+        let mut index = CacheIndex::default();
+        index.entries.insert(
+            "Foo".to_string(),
+            CacheEntry {
+                file: PathBuf::from("/tmp/foo.xml"),
+                fetched_at: 1_000,
+            },
+        );
+        assert!(index.is_fresh("Foo", DEFAULT_TTL, 1_500));
+        assert!(!index.is_fresh("Foo", DEFAULT_TTL, 1_000 + DEFAULT_TTL + 1));
+        assert!(!index.is_fresh("Bar", DEFAULT_TTL, 1_500));
+    }
+
+    #[test]
+    fn should_hash_url_deterministically() {
+        assert_eq!(
+            hash_url("https://example.com/feed.xml"),
+            hash_url("https://example.com/feed.xml")
+        );
+        assert_ne!(
+            hash_url("https://example.com/feed.xml"),
+            hash_url("https://example.com/other.xml")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! # Cli / source
+//!
+//! `tuifeed source add|rm|ls` subcommand, mutating the `[sources]` table in `config.toml`
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::config::Config;
+use crate::helpers::path as path_helper;
+use crate::opml;
+
+use clap::Subcommand;
+use std::path::PathBuf;
+
+/// ### SourceCommand
+///
+/// `tuifeed source <SUBCOMMAND>` variants
+#[derive(Debug, Subcommand)]
+pub enum SourceCommand {
+    /// Add a new feed source, or update an existing one
+    Add {
+        /// Name of the feed source
+        name: String,
+        /// Url of the feed source
+        url: String,
+    },
+    /// Remove an existing feed source
+    Rm {
+        /// Name of the feed source to remove
+        name: String,
+    },
+    /// List the configured feed sources
+    Ls,
+    /// Import feed sources from an OPML 2.0 file, merging them into `config.toml`
+    Import {
+        /// Path to the `.opml` file to import
+        file: PathBuf,
+    },
+    /// Export the configured feed sources to an OPML 2.0 file
+    Export {
+        /// Path of the `.opml` file to write
+        file: PathBuf,
+    },
+}
+
+/// ### run
+///
+/// Execute a `tuifeed source` subcommand against the user's `config.toml`
+pub fn run(command: SourceCommand) -> Result<(), String> {
+    let config_dir = path_helper::init_config_dir()?
+        .ok_or_else(|| String::from("could not resolve the configuration directory"))?;
+    let config_file = path_helper::get_config_file(config_dir.as_path())?;
+    let mut config = Config::load(config_file.as_path())?;
+    match command {
+        SourceCommand::Add { name, url } => {
+            config.add_source(&name, &url)?;
+            config.save(config_file.as_path())
+        }
+        SourceCommand::Rm { name } => {
+            config.remove_source(&name)?;
+            config.save(config_file.as_path())
+        }
+        SourceCommand::Ls => {
+            let mut sources: Vec<(&String, &String)> = config.sources.iter().collect();
+            sources.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, url) in sources {
+                println!("{}\t{}", name, url);
+            }
+            Ok(())
+        }
+        SourceCommand::Import { file } => {
+            let report = opml::import(file.as_path(), &mut config)?;
+            config.save(config_file.as_path())?;
+            println!("imported {} new source(s)", report.added);
+            if report.skipped > 0 {
+                eprintln!(
+                    "warning: skipped {} outline(s) missing a 'text' or 'xmlUrl' attribute",
+                    report.skipped
+                );
+            }
+            Ok(())
+        }
+        SourceCommand::Export { file } => opml::export(&config, file.as_path()),
+    }
+}
@@ -0,0 +1,146 @@
+//! # Config
+//!
+//! Feed sources configuration: (de)serializes the `[sources]` table in `config.toml`
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::helpers::file as file_helper;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// ### Config
+///
+/// Represents the parsed `config.toml`: the `[sources]` table, mapping a feed name to its
+/// url, plus the optional export template path and cache TTL
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Config {
+    #[serde(default)]
+    pub sources: HashMap<String, String>,
+    /// Path to the mustache-style template used to export articles.
+    /// Falls back to the built-in Markdown template when unset
+    #[serde(default)]
+    pub export_template: Option<String>,
+    /// How long, in seconds, a cached feed is served before it's refetched.
+    /// Falls back to [`crate::cache::DEFAULT_TTL`] when unset
+    #[serde(default)]
+    pub cache_ttl: Option<i64>,
+}
+
+impl Config {
+    /// ### load
+    ///
+    /// Read and parse `config.toml` from `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        toml::from_str(&data).map_err(|e| e.to_string())
+    }
+
+    /// ### save
+    ///
+    /// Serialize the configuration and write it back to `path` through `file_helper`
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = toml::to_string(self).map_err(|e| e.to_string())?;
+        file_helper::write_file(path, &data).map_err(|e| e.to_string())
+    }
+
+    /// ### add_source
+    ///
+    /// Insert or update a named feed source, rejecting malformed urls
+    pub fn add_source(&mut self, name: &str, url: &str) -> Result<(), String> {
+        if !is_valid_url(url) {
+            return Err(format!("'{}' is not a valid url", url));
+        }
+        self.sources.insert(name.to_string(), url.to_string());
+        Ok(())
+    }
+
+    /// ### remove_source
+    ///
+    /// Remove a named feed source, failing if it isn't configured
+    pub fn remove_source(&mut self, name: &str) -> Result<(), String> {
+        match self.sources.remove(name) {
+            Some(_) => Ok(()),
+            None => Err(format!("source '{}' doesn't exist", name)),
+        }
+    }
+}
+
+/// ### is_valid_url
+///
+/// Verify a feed url is well-formed before it gets persisted to `config.toml`
+fn is_valid_url(url: &str) -> bool {
+    url.parse::<url::Url>()
+        .map(|u| u.scheme() == "http" || u.scheme() == "https")
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_add_source() {
+        let mut config = Config::default();
+        assert!(config
+            .add_source("New_York_Times", "https://rss.nytimes.com/services/xml/rss/nyt/World.xml")
+            .is_ok());
+        assert_eq!(config.sources.len(), 1);
+    }
+
+    #[test]
+    fn should_reject_invalid_url() {
+        let mut config = Config::default();
+        assert!(config.add_source("bad", "not-a-url").is_err());
+        assert!(config.sources.is_empty());
+    }
+
+    #[test]
+    fn should_remove_source() {
+        let mut config = Config::default();
+        config
+            .add_source("Foo", "https://example.com/feed.xml")
+            .ok();
+        assert!(config.remove_source("Foo").is_ok());
+        assert!(config.remove_source("Foo").is_err());
+    }
+
+    #[test]
+    fn should_load_and_save_config() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-config.toml");
+        let mut config = Config::default();
+        config
+            .add_source("Foo", "https://example.com/feed.xml")
+            .ok();
+        assert!(config.save(tmp.as_path()).is_ok());
+        let loaded = Config::load(tmp.as_path()).ok().unwrap();
+        assert_eq!(loaded, config);
+        assert!(std::fs::remove_file(tmp.as_path()).is_ok());
+    }
+}
@@ -0,0 +1,213 @@
+//! # Export
+//!
+//! Renders an article to a file through a user-supplied mustache-style template
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::helpers::file as file_helper;
+use crate::helpers::hash as hash_helper;
+use crate::helpers::path as path_helper;
+
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// Built-in template used when the user hasn't configured one of their own
+const DEFAULT_TEMPLATE: &str = "# {{title}}\n\n*{{date}} - {{authors}}*\n\n{{link}}\n\n{{summary}}\n";
+
+/// ### ExportTemplate
+///
+/// Holds the raw markup used to render an article, with `{{title}}`, `{{date}}`,
+/// `{{authors}}`, `{{link}}` and `{{summary}}` placeholders
+pub struct ExportTemplate {
+    template: String,
+}
+
+impl Default for ExportTemplate {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl ExportTemplate {
+    /// ### load
+    ///
+    /// Load a template from `path`, falling back to the built-in Markdown template when `path` is `None`
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        match path {
+            Some(p) => std::fs::read_to_string(p)
+                .map(|template| Self { template })
+                .map_err(|e| e.to_string()),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// ### render
+    ///
+    /// Substitute the article placeholders into the template
+    pub fn render(
+        &self,
+        title: &str,
+        date: Option<DateTime<Local>>,
+        authors: &[String],
+        link: &str,
+        summary: &str,
+    ) -> String {
+        self.template
+            .replace("{{title}}", title)
+            .replace(
+                "{{date}}",
+                &date
+                    .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            )
+            .replace("{{authors}}", &authors.join(", "))
+            .replace("{{link}}", link)
+            .replace("{{summary}}", summary)
+    }
+}
+
+/// ### export_article
+///
+/// Render the article through `template` and write it to the export directory under
+/// `config_dir`, naming the file after the article title. Returns the written file path
+pub fn export_article(
+    config_dir: &Path,
+    template: &ExportTemplate,
+    title: &str,
+    date: Option<DateTime<Local>>,
+    authors: &[String],
+    link: &str,
+    summary: &str,
+) -> Result<PathBuf, String> {
+    let export_dir = path_helper::init_export_dir(config_dir)?;
+    let mut out_file = export_dir;
+    // Suffix with a stable hash of the article link so two articles sharing a title
+    // (e.g. reruns of the same headline) don't clobber each other's export
+    out_file.push(format!(
+        "{}-{}.md",
+        sanitize_filename(title),
+        &hash_helper::fnv1a_hex(link)[..8]
+    ));
+    let rendered = template.render(title, date, authors, link, summary);
+    file_helper::write_file(out_file.as_path(), &rendered).map_err(|e| e.to_string())?;
+    Ok(out_file)
+}
+
+/// ### sanitize_filename
+///
+/// Turn an article title into a filesystem-safe file name
+fn sanitize_filename(title: &str) -> String {
+    let sanitized: String = title
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if sanitized.is_empty() {
+        "article".to_string()
+    } else {
+        sanitized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_render_default_template() {
+        let template = ExportTemplate::default();
+        let rendered = template.render(
+            "Hello world",
+            None,
+            &["Jane Doe".to_string()],
+            "https://example.com",
+            "A summary",
+        );
+        assert!(rendered.contains("# Hello world"));
+        assert!(rendered.contains("Jane Doe"));
+        assert!(rendered.contains("https://example.com"));
+        assert!(rendered.contains("A summary"));
+    }
+
+    #[test]
+    fn should_sanitize_filename() {
+        assert_eq!(sanitize_filename("Hello, world!"), "Hello__world_");
+        assert_eq!(sanitize_filename("   "), "article");
+    }
+
+    #[test]
+    fn should_export_article_to_file() {
+        let config_dir = std::env::temp_dir();
+        let template = ExportTemplate::default();
+        let written = export_article(
+            config_dir.as_path(),
+            &template,
+            "Test Article",
+            None,
+            &["Jane Doe".to_string()],
+            "https://example.com",
+            "Summary text",
+        )
+        .ok()
+        .unwrap();
+        assert!(written.exists());
+        assert!(std::fs::remove_file(written.as_path()).is_ok());
+    }
+
+    #[test]
+    fn should_not_collide_on_shared_title() {
+        let config_dir = std::env::temp_dir();
+        let template = ExportTemplate::default();
+        let first = export_article(
+            config_dir.as_path(),
+            &template,
+            "Breaking News",
+            None,
+            &[],
+            "https://example.com/a",
+            "Summary A",
+        )
+        .ok()
+        .unwrap();
+        let second = export_article(
+            config_dir.as_path(),
+            &template,
+            "Breaking News",
+            None,
+            &[],
+            "https://example.com/b",
+            "Summary B",
+        )
+        .ok()
+        .unwrap();
+        assert_ne!(first, second);
+        assert!(std::fs::remove_file(first.as_path()).is_ok());
+        assert!(std::fs::remove_file(second.as_path()).is_ok());
+    }
+}
@@ -0,0 +1,177 @@
+//! # Feed
+//!
+//! Serves a source's feed XML from the offline cache, refetching it once the cached copy
+//! is older than its TTL, and falling back to the last cached copy when a fetch fails
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::cache::CacheIndex;
+
+#[cfg(test)]
+use crate::helpers::hash as hash_helper;
+
+use std::path::Path;
+
+/// ### fetch
+///
+/// Return `source_name`'s feed XML: serve the cache immediately if its entry is younger
+/// than `ttl` seconds (relative to `now`); otherwise fetch a fresh copy through `fetch_fn`
+/// and update the cache. If `fetch_fn` fails (e.g. the network is unreachable), fall back
+/// to the last cached copy, however stale, so articles stay browsable offline
+pub fn fetch(
+    cache_dir: &Path,
+    index: &mut CacheIndex,
+    source_name: &str,
+    source_url: &str,
+    ttl: i64,
+    now: i64,
+    fetch_fn: impl FnOnce(&str) -> Result<String, String>,
+) -> Result<String, String> {
+    if index.is_fresh(source_name, ttl, now) {
+        if let Some(cached) = index.read(source_name)? {
+            return Ok(cached);
+        }
+    }
+    match fetch_fn(source_url) {
+        Ok(xml) => {
+            index.put(cache_dir, source_name, source_url, &xml, now)?;
+            Ok(xml)
+        }
+        Err(err) => index.read(source_name)?.ok_or(err),
+    }
+}
+
+/// ### http_get
+///
+/// Fetch a source's feed XML over HTTP(S); the `fetch_fn` passed to [`fetch`] on startup
+pub fn http_get(url: &str) -> Result<String, String> {
+    ureq::get(url)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_serve_fresh_cache_without_fetching() {
+        let cache_dir = std::env::temp_dir();
+        let mut index = CacheIndex::default();
+        index
+            .put(cache_dir.as_path(), "Foo", "https://example.com/feed.xml", "<rss>cached</rss>", 1_000)
+            .ok();
+        let xml = fetch(
+            cache_dir.as_path(),
+            &mut index,
+            "Foo",
+            "https://example.com/feed.xml",
+            3_600,
+            1_100,
+            |_| panic!("should not fetch a still-fresh source"),
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(xml, "<rss>cached</rss>");
+        cleanup(&index, cache_dir.as_path());
+    }
+
+    #[test]
+    fn should_refetch_stale_cache_and_update_it() {
+        let cache_dir = std::env::temp_dir();
+        let mut index = CacheIndex::default();
+        index
+            .put(cache_dir.as_path(), "Foo", "https://example.com/feed.xml", "<rss>old</rss>", 1_000)
+            .ok();
+        let xml = fetch(
+            cache_dir.as_path(),
+            &mut index,
+            "Foo",
+            "https://example.com/feed.xml",
+            10,
+            2_000,
+            |_| Ok("<rss>new</rss>".to_string()),
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(xml, "<rss>new</rss>");
+        assert_eq!(
+            index.read("Foo").ok().unwrap(),
+            Some("<rss>new</rss>".to_string())
+        );
+        cleanup(&index, cache_dir.as_path());
+    }
+
+    #[test]
+    fn should_fall_back_to_cache_when_fetch_fails() {
+        let cache_dir = std::env::temp_dir();
+        let mut index = CacheIndex::default();
+        index
+            .put(cache_dir.as_path(), "Foo", "https://example.com/feed.xml", "<rss>old</rss>", 1_000)
+            .ok();
+        let xml = fetch(
+            cache_dir.as_path(),
+            &mut index,
+            "Foo",
+            "https://example.com/feed.xml",
+            10,
+            2_000,
+            |_| Err("network unreachable".to_string()),
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(xml, "<rss>old</rss>");
+        cleanup(&index, cache_dir.as_path());
+    }
+
+    #[test]
+    fn should_propagate_error_with_no_cache_to_fall_back_to() {
+        let cache_dir = std::env::temp_dir();
+        let mut index = CacheIndex::default();
+        let result = fetch(
+            cache_dir.as_path(),
+            &mut index,
+            "Bar",
+            "https://example.com/other.xml",
+            10,
+            2_000,
+            |_| Err("network unreachable".to_string()),
+        );
+        assert_eq!(result, Err("network unreachable".to_string()));
+    }
+
+    fn cleanup(_index: &CacheIndex, cache_dir: &Path) {
+        let cached_file = cache_dir.join(format!(
+            "{}.xml",
+            hash_helper::fnv1a_hex("https://example.com/feed.xml")
+        ));
+        std::fs::remove_file(cached_file).ok();
+        std::fs::remove_file(cache_dir.join("index.toml")).ok();
+    }
+}
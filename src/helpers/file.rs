@@ -0,0 +1,66 @@
+//! # File
+//!
+//! Filesystem helpers shared by every module that persists state to disk
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::path::Path;
+
+/// ### write_file
+///
+/// Write `data` to `path`, creating any missing parent directories first
+pub fn write_file(path: &Path, data: &str) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::write(path, data)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_write_file() {
+        let path = std::env::temp_dir().join("tuifeed-test-write-file.txt");
+        assert!(write_file(path.as_path(), "hello").is_ok());
+        assert_eq!(std::fs::read_to_string(path.as_path()).unwrap(), "hello");
+        std::fs::remove_file(path.as_path()).ok();
+    }
+
+    #[test]
+    fn should_create_missing_parent_dirs() {
+        let dir = std::env::temp_dir().join("tuifeed-test-write-file-parent");
+        let path = dir.join("nested/state.toml");
+        std::fs::remove_dir_all(dir.as_path()).ok();
+        assert!(write_file(path.as_path(), "data").is_ok());
+        assert_eq!(std::fs::read_to_string(path.as_path()).unwrap(), "data");
+        std::fs::remove_dir_all(dir.as_path()).ok();
+    }
+}
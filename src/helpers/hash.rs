@@ -0,0 +1,69 @@
+//! # Hash
+//!
+//! Small stable-hash helper for on-disk cache/export keys. `std::collections::hash_map::
+//! DefaultHasher`'s algorithm isn't guaranteed stable across Rust releases, so it can't be
+//! used to derive keys that need to stay the same across runs
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+/// ### fnv1a
+///
+/// Hash `data` with the 64-bit FNV-1a algorithm, which is deterministic across Rust
+/// versions, platforms and process runs, unlike `DefaultHasher`
+pub fn fnv1a(data: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    data.bytes()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// ### fnv1a_hex
+///
+/// Hex-encode the FNV-1a hash of `data`, handy for building filesystem-safe cache/export keys
+pub fn fnv1a_hex(data: &str) -> String {
+    format!("{:016x}", fnv1a(data))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_hash_deterministically() {
+        assert_eq!(fnv1a("https://example.com/feed.xml"), fnv1a("https://example.com/feed.xml"));
+        assert_eq!(fnv1a_hex("https://example.com/feed.xml"), fnv1a_hex("https://example.com/feed.xml"));
+    }
+
+    #[test]
+    fn should_differ_for_different_input() {
+        assert_ne!(
+            fnv1a("https://example.com/feed.xml"),
+            fnv1a("https://example.com/other.xml")
+        );
+    }
+}
@@ -60,6 +60,37 @@ pub fn init_config_dir() -> Result<Option<PathBuf>, String> {
     }
 }
 
+/// ### init_cache_dir
+///
+/// Get tuifeed cache directory path, creating it if it doesn't exist.
+/// Returns None, if it's not possible to get it
+pub fn init_cache_dir() -> Result<Option<PathBuf>, String> {
+    #[cfg(not(test))]
+    lazy_static! {
+        static ref CACHE_DIR: Option<PathBuf> = dirs::cache_dir();
+    }
+    #[cfg(test)]
+    lazy_static! {
+        static ref CACHE_DIR: Option<PathBuf> = Some(std::env::temp_dir());
+    }
+    if CACHE_DIR.is_some() {
+        let mut p: PathBuf = CACHE_DIR.as_ref().unwrap().clone();
+        #[cfg(not(test))]
+        p.push("tuifeed/");
+        #[cfg(test)]
+        p.push("tuifeed-cache/");
+        match p.exists() {
+            true => Ok(Some(p)),
+            false => match std::fs::create_dir(p.as_path()) {
+                Ok(_) => Ok(Some(p)),
+                Err(err) => Err(err.to_string()),
+            },
+        }
+    } else {
+        Ok(None)
+    }
+}
+
 /// ### get_config_path
 ///
 /// Returns path for config file.
@@ -89,6 +120,41 @@ fn init_config_file(p: &Path) -> Result<(), String> {
     .map_err(|e| e.to_string())
 }
 
+/// ### get_state_file
+///
+/// Returns path for the read/unread state file, next to `config.toml`.
+/// If the file doesn't exist, it will initialize it
+pub fn get_state_file(config_dir: &Path) -> Result<PathBuf, String> {
+    let mut state_file: PathBuf = PathBuf::from(config_dir);
+    state_file.push("state.toml");
+    if !state_file.exists() {
+        init_state_file(state_file.as_path())?
+    }
+    Ok(state_file)
+}
+
+/// ### init_state_file
+///
+/// Initialize an empty read/unread state file
+fn init_state_file(p: &Path) -> Result<(), String> {
+    file_helper::write_file(p, "").map_err(|e| e.to_string())
+}
+
+/// ### init_export_dir
+///
+/// Get the directory articles get exported to, creating it under `config_dir` if missing
+pub fn init_export_dir(config_dir: &Path) -> Result<PathBuf, String> {
+    let mut p: PathBuf = PathBuf::from(config_dir);
+    p.push("exports/");
+    match p.exists() {
+        true => Ok(p),
+        false => match std::fs::create_dir(p.as_path()) {
+            Ok(_) => Ok(p),
+            Err(err) => Err(err.to_string()),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -141,4 +207,36 @@ mod tests {
         );
         assert!(std::fs::remove_dir_all(conf_dir.as_path()).is_ok());
     }
+
+    #[test]
+    #[serial]
+    fn should_get_state_file() {
+        let conf_dir: PathBuf = init_config_dir().ok().unwrap().unwrap();
+        let state_file = get_state_file(conf_dir.as_path()).ok().unwrap();
+        assert_eq!(
+            format!("{}", state_file.display()),
+            format!("{}state.toml", conf_dir.display())
+        );
+        assert!(std::fs::remove_dir_all(conf_dir.as_path()).is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn should_get_cache_dir() {
+        let cache_dir: PathBuf = init_cache_dir().ok().unwrap().unwrap();
+        assert!(cache_dir.exists());
+        assert!(std::fs::remove_dir_all(cache_dir.as_path()).is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn should_get_export_dir() {
+        let conf_dir: PathBuf = init_config_dir().ok().unwrap().unwrap();
+        let export_dir = init_export_dir(conf_dir.as_path()).ok().unwrap();
+        assert_eq!(
+            format!("{}", export_dir.display()),
+            format!("{}exports/", conf_dir.display())
+        );
+        assert!(std::fs::remove_dir_all(conf_dir.as_path()).is_ok());
+    }
 }
@@ -0,0 +1,73 @@
+//! # tuifeed
+//!
+//! Parses command line arguments: either dispatches a management subcommand, or hands off
+//! to the reader (`ui::run`)
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+mod cache;
+mod cli;
+mod config;
+mod export;
+mod feed;
+mod helpers;
+mod opml;
+mod state;
+mod ui;
+
+use cli::SourceCommand;
+
+use clap::{Parser, Subcommand};
+
+/// ### Args
+///
+/// tuifeed command line arguments
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// ### Command
+///
+/// Top-level subcommands; when none is given, tuifeed hands off to the reader (`ui::run`)
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Manage feed sources (add/rm/ls/import/export) without entering the reader
+    #[command(subcommand)]
+    Source(SourceCommand),
+}
+
+fn main() {
+    let args = Args::parse();
+    let result = match args.command {
+        Some(Command::Source(cmd)) => cli::run_source(cmd),
+        None => ui::run(),
+    };
+    if let Err(err) = result {
+        eprintln!("tuifeed: {}", err);
+        std::process::exit(1);
+    }
+}
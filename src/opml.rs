@@ -0,0 +1,273 @@
+//! # Opml
+//!
+//! OPML 2.0 import/export for feed subscriptions
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::config::Config;
+use crate::helpers::file as file_helper;
+
+use std::collections::HashSet;
+use std::path::Path;
+
+/// ### ImportReport
+///
+/// Outcome of an OPML import: how many sources were merged in, and how many `<outline>`-like
+/// tags were found but could not be parsed (e.g. missing `xmlUrl`/`text`), so the caller can
+/// warn the user instead of silently dropping entries
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub added: usize,
+    pub skipped: usize,
+}
+
+/// ### import
+///
+/// Parse an OPML file's `<outline xmlUrl="..." text="...">` entries and merge them into
+/// `config`'s `[sources]` table, de-duplicating by url and sanitizing names into valid TOML
+/// keys. Existing sources are left untouched
+pub fn import(path: &Path, config: &mut Config) -> Result<ImportReport, String> {
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut existing_urls: HashSet<String> = config.sources.values().cloned().collect();
+    let mut report = ImportReport::default();
+    let (outlines, skipped) = parse_outlines(&data);
+    report.skipped = skipped;
+    for (name, url) in outlines {
+        if !existing_urls.insert(url.clone()) {
+            continue;
+        }
+        let key = unique_key(&sanitize_key(&name), config);
+        config.sources.insert(key, url);
+        report.added += 1;
+    }
+    Ok(report)
+}
+
+/// ### export
+///
+/// Walk `config`'s sources and write an OPML 2.0 document, with one `<outline>` per feed, to `path`
+pub fn export(config: &Config, path: &Path) -> Result<(), String> {
+    let mut names: Vec<&String> = config.sources.keys().collect();
+    names.sort();
+    let mut body = String::new();
+    for name in names {
+        let url = &config.sources[name];
+        body.push_str(&format!(
+            "    <outline type=\"rss\" text=\"{0}\" title=\"{0}\" xmlUrl=\"{1}\"/>\n",
+            escape_xml(name),
+            escape_xml(url)
+        ));
+    }
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<opml version=\"2.0\">\n  <head>\n    <title>tuifeed subscriptions</title>\n  </head>\n  <body>\n{}  </body>\n</opml>\n",
+        body
+    );
+    file_helper::write_file(path, &document).map_err(|e| e.to_string())
+}
+
+/// ### parse_outlines
+///
+/// Extract `(text, xmlUrl)` pairs from `<outline>` elements in raw OPML markup. Each `<outline`
+/// tag is read up to its closing `>`, so attributes spread across multiple lines (or tags
+/// nested under a category `<outline>` with no `xmlUrl`) are handled the same way a single-line
+/// tag would be. Returns the parsed outlines plus a count of `<outline` tags that were found
+/// but didn't carry both `text` and `xmlUrl`, so the caller can warn instead of dropping silently
+fn parse_outlines(data: &str) -> (Vec<(String, String)>, usize) {
+    let mut outlines = Vec::new();
+    let mut skipped = 0;
+    let mut rest = data;
+    while let Some(start) = rest.find("<outline") {
+        rest = &rest[start..];
+        let tag = match rest.find('>') {
+            Some(end) => &rest[..=end],
+            None => break,
+        };
+        match (extract_attr(tag, "xmlUrl"), extract_attr(tag, "text")) {
+            (Some(url), Some(text)) => {
+                outlines.push((unescape_xml(&text), unescape_xml(&url)));
+            }
+            _ => skipped += 1,
+        }
+        rest = &rest[tag.len()..];
+    }
+    (outlines, skipped)
+}
+
+/// ### extract_attr
+///
+/// Pull the value of `attr="..."` out of a single OPML tag
+fn extract_attr(tag: &str, attr: &str) -> Option<String> {
+    let needle = format!("{}=\"", attr);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+/// ### sanitize_key
+///
+/// Turn a feed title into a valid bare TOML key by replacing anything that isn't
+/// alphanumeric, `-` or `_` with `_`
+fn sanitize_key(name: &str) -> String {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if sanitized.is_empty() {
+        "source".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// ### unique_key
+///
+/// Disambiguate `key` against `config`'s existing source names by appending a counter
+fn unique_key(key: &str, config: &Config) -> String {
+    if !config.sources.contains_key(key) {
+        return key.to_string();
+    }
+    let mut i = 1;
+    loop {
+        let candidate = format!("{}_{}", key, i);
+        if !config.sources.contains_key(&candidate) {
+            return candidate;
+        }
+        i += 1;
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    const OPML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <body>
+    <outline type="rss" text="New York Times" title="New York Times" xmlUrl="https://rss.nytimes.com/services/xml/rss/nyt/World.xml"/>
+    <outline type="rss" text="Foo" title="Foo" xmlUrl="https://example.com/feed.xml"/>
+  </body>
+</opml>
+"#;
+
+    const MULTILINE_OPML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <body>
+    <outline
+        type="rss"
+        text="Multi Line"
+        title="Multi Line"
+        xmlUrl="https://example.com/multiline.xml" />
+    <outline text="Tech" title="Tech">
+      <outline type="rss" text="Nested" title="Nested" xmlUrl="https://example.com/nested.xml"/>
+    </outline>
+  </body>
+</opml>
+"#;
+
+    #[test]
+    fn should_import_opml() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-import.opml");
+        std::fs::write(tmp.as_path(), OPML).ok();
+        let mut config = Config::default();
+        let report = import(tmp.as_path(), &mut config).ok().unwrap();
+        assert_eq!(report.added, 2);
+        assert_eq!(report.skipped, 0);
+        assert_eq!(
+            config.sources.get("New_York_Times").map(|s| s.as_str()),
+            Some("https://rss.nytimes.com/services/xml/rss/nyt/World.xml")
+        );
+        assert_eq!(
+            config.sources.get("Foo").map(|s| s.as_str()),
+            Some("https://example.com/feed.xml")
+        );
+        std::fs::remove_file(tmp.as_path()).ok();
+    }
+
+    #[test]
+    fn should_not_duplicate_existing_url_on_import() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-import-dedup.opml");
+        std::fs::write(tmp.as_path(), OPML).ok();
+        let mut config = Config::default();
+        config
+            .add_source("Foo", "https://example.com/feed.xml")
+            .ok();
+        let report = import(tmp.as_path(), &mut config).ok().unwrap();
+        assert_eq!(report.added, 1);
+        assert_eq!(config.sources.len(), 2);
+        std::fs::remove_file(tmp.as_path()).ok();
+    }
+
+    #[test]
+    fn should_import_multiline_and_report_skipped_category_outlines() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-import-multiline.opml");
+        std::fs::write(tmp.as_path(), MULTILINE_OPML).ok();
+        let mut config = Config::default();
+        let report = import(tmp.as_path(), &mut config).ok().unwrap();
+        // "Multi Line" and "Nested" are real feeds; the "Tech" category outline carries no
+        // xmlUrl and is reported as skipped rather than silently dropped
+        assert_eq!(report.added, 2);
+        assert_eq!(report.skipped, 1);
+        assert!(config
+            .sources
+            .values()
+            .any(|u| u == "https://example.com/multiline.xml"));
+        assert!(config
+            .sources
+            .values()
+            .any(|u| u == "https://example.com/nested.xml"));
+        std::fs::remove_file(tmp.as_path()).ok();
+    }
+
+    #[test]
+    fn should_export_opml() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-export.opml");
+        let mut config = Config::default();
+        config
+            .add_source("Foo", "https://example.com/feed.xml")
+            .ok();
+        assert!(export(&config, tmp.as_path()).is_ok());
+        let data = std::fs::read_to_string(tmp.as_path()).ok().unwrap();
+        assert!(data.contains("xmlUrl=\"https://example.com/feed.xml\""));
+        std::fs::remove_file(tmp.as_path()).ok();
+    }
+}
@@ -0,0 +1,116 @@
+//! # State
+//!
+//! Persisted read/unread state for articles, keyed by article link
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::helpers::file as file_helper;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// ### ReadState
+///
+/// Tracks which article links the user has already opened, persisted to `state.toml`
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ReadState {
+    #[serde(default)]
+    read: HashSet<String>,
+}
+
+impl ReadState {
+    /// ### load
+    ///
+    /// Load read state from `path`, starting from an empty state if the file is empty or missing
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        if data.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        toml::from_str(&data).map_err(|e| e.to_string())
+    }
+
+    /// ### save
+    ///
+    /// Serialize the state and write it back to `path` through `file_helper`
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = toml::to_string(self).map_err(|e| e.to_string())?;
+        file_helper::write_file(path, &data).map_err(|e| e.to_string())
+    }
+
+    /// ### is_read
+    ///
+    /// Whether the article at `link` has already been opened
+    pub fn is_read(&self, link: &str) -> bool {
+        self.read.contains(link)
+    }
+
+    /// ### mark_read
+    ///
+    /// Mark `link` as read. Returns `true` if this changed the state, so callers know
+    /// whether a `save` is actually needed
+    pub fn mark_read(&mut self, link: &str) -> bool {
+        self.read.insert(link.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_mark_and_check_read() {
+        let mut state = ReadState::default();
+        assert!(!state.is_read("https://example.com/a"));
+        assert!(state.mark_read("https://example.com/a"));
+        assert!(state.is_read("https://example.com/a"));
+        assert!(!state.mark_read("https://example.com/a"));
+    }
+
+    #[test]
+    fn should_load_and_save_state() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-state.toml");
+        let mut state = ReadState::default();
+        state.mark_read("https://example.com/a");
+        assert!(state.save(tmp.as_path()).is_ok());
+        let loaded = ReadState::load(tmp.as_path()).ok().unwrap();
+        assert_eq!(loaded, state);
+        assert!(std::fs::remove_file(tmp.as_path()).is_ok());
+    }
+
+    #[test]
+    fn should_load_empty_state_when_missing() {
+        let tmp = std::env::temp_dir().join("tuifeed-test-state-missing.toml");
+        assert!(!tmp.exists());
+        let state = ReadState::load(tmp.as_path()).ok().unwrap();
+        assert_eq!(state, ReadState::default());
+    }
+}
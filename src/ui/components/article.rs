@@ -44,12 +44,21 @@ pub struct ArticleTitle {
 }
 
 impl ArticleTitle {
-    pub fn new(title: &str) -> Self {
+    /// ### new
+    ///
+    /// Instantiate a new `ArticleTitle`. `read` dims the title for articles the user has
+    /// already opened, so unread articles stand out
+    pub fn new(title: &str, read: bool) -> Self {
+        let (color, modifiers) = if read {
+            (Color::Gray, TextModifiers::empty())
+        } else {
+            (Color::LightYellow, TextModifiers::BOLD)
+        };
         Self {
             component: Paragraph::default()
                 .borders(Borders::default().sides(BorderSides::empty()))
-                .foreground(Color::LightYellow)
-                .modifiers(TextModifiers::BOLD)
+                .foreground(color)
+                .modifiers(modifiers)
                 .text(&[TextSpan::from(title)]),
         }
     }
@@ -67,10 +76,15 @@ pub struct ArticleDate {
 }
 
 impl ArticleDate {
-    pub fn new(datetime: Option<DateTime<Local>>) -> Self {
+    /// ### new
+    ///
+    /// Instantiate a new `ArticleDate`. `read` dims the date for articles the user has
+    /// already opened, matching `ArticleTitle`
+    pub fn new(datetime: Option<DateTime<Local>>, read: bool) -> Self {
+        let color = if read { Color::Gray } else { Color::LightGreen };
         Self {
             component: Label::default()
-                .foreground(Color::LightGreen)
+                .foreground(color)
                 .modifiers(TextModifiers::BOLD | TextModifiers::ITALIC)
                 .text(
                     datetime
@@ -207,6 +221,10 @@ impl Component<Msg, NoUserEvent> for ArticleSummary {
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => Some(Msg::OpenArticle),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('s'),
+                ..
+            }) => Some(Msg::ExportArticle),
             _ => None,
         }
     }
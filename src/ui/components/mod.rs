@@ -0,0 +1,43 @@
+//! # Components
+//!
+//! Reader UI components and the messages they exchange with the application model
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+pub mod article;
+
+/// ### Msg
+///
+/// Messages emitted by reader components and handled by the application model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Msg {
+    /// Nothing to do, but the component consumed the event
+    None,
+    /// The user left the article view, back to the article list
+    ArticleBlur,
+    /// The user asked to open the focused article (e.g. in a browser)
+    OpenArticle,
+    /// The user asked to export the focused article to a file
+    ExportArticle,
+}
@@ -0,0 +1,324 @@
+//! # Ui
+//!
+//! The interactive reader: components, the application model and its entry point
+
+/**
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+pub mod components;
+
+use components::Msg;
+use crate::cache::{self, CacheIndex};
+use crate::config::Config;
+use crate::export::{self, ExportTemplate};
+use crate::feed;
+use crate::helpers::path as path_helper;
+use crate::state::ReadState;
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+
+/// ### ArticleContext
+///
+/// The data of the article currently focused in the reader; enough to export it or mark
+/// it read
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArticleContext {
+    pub title: String,
+    pub date: Option<DateTime<Local>>,
+    pub authors: Vec<String>,
+    pub link: String,
+    pub summary: String,
+}
+
+/// ### Model
+///
+/// Holds the reader's configuration and the article currently focused, and dispatches
+/// [`Msg`] coming from components to the right action
+pub struct Model {
+    config_dir: PathBuf,
+    config: Config,
+    cache_dir: Option<PathBuf>,
+    cache: CacheIndex,
+    state: ReadState,
+    current_article: Option<ArticleContext>,
+}
+
+impl Model {
+    /// ### new
+    pub fn new(config_dir: PathBuf, config: Config, cache_dir: Option<PathBuf>) -> Self {
+        let cache = cache_dir
+            .as_deref()
+            .and_then(|dir| CacheIndex::load(dir).ok())
+            .unwrap_or_default();
+        let state = path_helper::get_state_file(config_dir.as_path())
+            .ok()
+            .and_then(|file| ReadState::load(file.as_path()).ok())
+            .unwrap_or_default();
+        Self {
+            config_dir,
+            config,
+            cache_dir,
+            cache,
+            state,
+            current_article: None,
+        }
+    }
+
+    /// ### is_read
+    ///
+    /// Whether the article at `link` has already been opened, so components can be built
+    /// with the right `read` flag
+    pub fn is_read(&self, link: &str) -> bool {
+        self.state.is_read(link)
+    }
+
+    /// ### refresh_sources
+    ///
+    /// Serve each configured source from the offline cache when it's still fresh, and
+    /// refetch it through `fetch_fn` otherwise, falling back to the stale cached copy if
+    /// the fetch fails. Returns each source's name paired with its feed XML or fetch error.
+    /// Does nothing (and returns an empty list) if the cache directory couldn't be resolved
+    pub fn refresh_sources(
+        &mut self,
+        now: i64,
+        fetch_fn: impl Fn(&str) -> Result<String, String>,
+    ) -> Vec<(String, Result<String, String>)> {
+        let cache_dir = match self.cache_dir.clone() {
+            Some(dir) => dir,
+            None => return Vec::new(),
+        };
+        let ttl = self.config.cache_ttl.unwrap_or(cache::DEFAULT_TTL);
+        let mut sources: Vec<(String, String)> = self
+            .config
+            .sources
+            .iter()
+            .map(|(name, url)| (name.clone(), url.clone()))
+            .collect();
+        sources.sort();
+        sources
+            .into_iter()
+            .map(|(name, url)| {
+                let xml = feed::fetch(cache_dir.as_path(), &mut self.cache, &name, &url, ttl, now, &fetch_fn);
+                (name, xml)
+            })
+            .collect()
+    }
+
+    /// ### focus_article
+    ///
+    /// Set the article currently focused in the reader, i.e. the one shown in the
+    /// `ArticleSummary` view. This is the article's first view, so mark it read right away;
+    /// `Msg::OpenArticle` still marks it too, but that's then a no-op
+    pub fn focus_article(&mut self, article: ArticleContext) {
+        self.current_article = Some(article);
+        if let Err(err) = self.mark_current_article_read() {
+            eprintln!("tuifeed: failed to persist read state: {}", err);
+        }
+    }
+
+    /// ### update
+    ///
+    /// Handle a [`Msg`] emitted by a component
+    pub fn update(&mut self, msg: Msg) -> Option<Msg> {
+        match msg {
+            Msg::ExportArticle => {
+                if let Err(err) = self.export_current_article() {
+                    eprintln!("tuifeed: failed to export article: {}", err);
+                }
+                Some(Msg::None)
+            }
+            Msg::OpenArticle => {
+                if let Err(err) = self.mark_current_article_read() {
+                    eprintln!("tuifeed: failed to persist read state: {}", err);
+                }
+                Some(Msg::None)
+            }
+            Msg::None | Msg::ArticleBlur => None,
+        }
+    }
+
+    /// ### mark_current_article_read
+    ///
+    /// Mark the focused article's link as read and persist the state, if this is the
+    /// first time it's been opened. Does nothing if no article is focused
+    fn mark_current_article_read(&mut self) -> Result<(), String> {
+        let link = match self.current_article.as_ref() {
+            Some(article) => article.link.clone(),
+            None => return Ok(()),
+        };
+        if self.state.mark_read(&link) {
+            let state_file = path_helper::get_state_file(self.config_dir.as_path())?;
+            self.state.save(state_file.as_path())?;
+        }
+        Ok(())
+    }
+
+    /// ### export_current_article
+    ///
+    /// Render the focused article through the configured (or built-in) template and write
+    /// it to the export directory
+    fn export_current_article(&self) -> Result<PathBuf, String> {
+        let article = self
+            .current_article
+            .as_ref()
+            .ok_or_else(|| String::from("no article is currently focused"))?;
+        let template = ExportTemplate::load(
+            self.config.export_template.as_deref().map(Path::new),
+        )?;
+        export::export_article(
+            self.config_dir.as_path(),
+            &template,
+            &article.title,
+            article.date,
+            &article.authors,
+            &article.link,
+            &article.summary,
+        )
+    }
+}
+
+/// ### run
+///
+/// Resolve the configuration directory, load `config.toml`, build the reader's [`Model`] and
+/// refresh every source through the offline cache, reporting failed refreshes on stderr.
+/// No terminal UI is drawn and no event loop is run: it returns once the refresh is done
+pub fn run() -> Result<(), String> {
+    let config_dir = path_helper::init_config_dir()?
+        .ok_or_else(|| String::from("could not resolve the configuration directory"))?;
+    let config_file = path_helper::get_config_file(config_dir.as_path())?;
+    let config: Config = Config::load(config_file.as_path())?;
+    let cache_dir = path_helper::init_cache_dir()?;
+    let mut model = Model::new(config_dir, config, cache_dir);
+    // Serve cached feeds immediately on startup, refetching whatever's past its TTL
+    let now = Local::now().timestamp();
+    for (name, result) in model.refresh_sources(now, feed::http_get) {
+        if let Err(err) = result {
+            eprintln!("tuifeed: failed to refresh '{}': {}", name, err);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_export_focused_article_on_msg() {
+        let config_dir = std::env::temp_dir();
+        let mut model = Model::new(config_dir.clone(), Config::default(), None);
+        model.focus_article(ArticleContext {
+            title: "Model Export Test".to_string(),
+            date: None,
+            authors: vec!["Jane Doe".to_string()],
+            link: "https://example.com/model-export-test".to_string(),
+            summary: "A summary".to_string(),
+        });
+        assert_eq!(model.update(Msg::ExportArticle), Some(Msg::None));
+        let export_dir = config_dir.join("exports");
+        let written: Vec<_> = std::fs::read_dir(export_dir.as_path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with("Model_Export_Test")
+            })
+            .collect();
+        assert_eq!(written.len(), 1);
+        std::fs::remove_file(written[0].path()).ok();
+    }
+
+    #[test]
+    fn should_report_error_when_nothing_is_focused() {
+        let config_dir = std::env::temp_dir();
+        let model = Model::new(config_dir, Config::default(), None);
+        assert!(model.export_current_article().is_err());
+    }
+
+    #[test]
+    fn should_refresh_sources_through_the_cache() {
+        let config_dir = std::env::temp_dir();
+        let cache_dir = std::env::temp_dir().join("tuifeed-test-model-cache");
+        std::fs::create_dir_all(cache_dir.as_path()).ok();
+        let mut config = Config::default();
+        config
+            .add_source("Foo", "https://example.com/feed.xml")
+            .ok();
+        let mut model = Model::new(config_dir, config, Some(cache_dir.clone()));
+        let results = model.refresh_sources(1_000, |_| Ok("<rss>fresh</rss>".to_string()));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "Foo");
+        assert_eq!(results[0].1, Ok("<rss>fresh</rss>".to_string()));
+        std::fs::remove_dir_all(cache_dir.as_path()).ok();
+    }
+
+    #[test]
+    fn should_mark_article_read_on_open_msg() {
+        let config_dir = std::env::temp_dir().join("tuifeed-test-model-state");
+        std::fs::create_dir_all(config_dir.as_path()).ok();
+        let mut model = Model::new(config_dir.clone(), Config::default(), None);
+        assert!(!model.is_read("https://example.com/read-state-test"));
+        // Set the article directly: `focus_article` would already mark it read
+        model.current_article = Some(ArticleContext {
+            title: "Read State Test".to_string(),
+            date: None,
+            authors: vec![],
+            link: "https://example.com/read-state-test".to_string(),
+            summary: "A summary".to_string(),
+        });
+        assert_eq!(model.update(Msg::OpenArticle), Some(Msg::None));
+        assert!(model.is_read("https://example.com/read-state-test"));
+        std::fs::remove_dir_all(config_dir.as_path()).ok();
+    }
+
+    #[test]
+    fn should_mark_article_read_as_soon_as_it_is_focused() {
+        let config_dir = std::env::temp_dir().join("tuifeed-test-model-state-focus");
+        std::fs::create_dir_all(config_dir.as_path()).ok();
+        let mut model = Model::new(config_dir.clone(), Config::default(), None);
+        assert!(!model.is_read("https://example.com/focus-only-test"));
+        model.focus_article(ArticleContext {
+            title: "Focus Only Test".to_string(),
+            date: None,
+            authors: vec![],
+            link: "https://example.com/focus-only-test".to_string(),
+            summary: "A summary".to_string(),
+        });
+        // Read without an explicit OpenArticle (Enter) - the summary view was the first read
+        assert!(model.is_read("https://example.com/focus-only-test"));
+        std::fs::remove_dir_all(config_dir.as_path()).ok();
+    }
+
+    #[test]
+    fn should_do_nothing_on_open_msg_without_a_focused_article() {
+        let config_dir = std::env::temp_dir().join("tuifeed-test-model-state-unfocused");
+        std::fs::create_dir_all(config_dir.as_path()).ok();
+        let mut model = Model::new(config_dir.clone(), Config::default(), None);
+        assert_eq!(model.update(Msg::OpenArticle), Some(Msg::None));
+        std::fs::remove_dir_all(config_dir.as_path()).ok();
+    }
+}